@@ -0,0 +1,1186 @@
+use clap::Parser;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen, size},
+};
+use serde::Deserialize;
+use std::{
+    collections::VecDeque,
+    error::Error,
+    io::{stdout, Write},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+use std::f32::consts::PI;
+use std::fs;
+use std::path::Path;
+
+// Largest pitch magnitude the camera may take: 0.001 rad short of straight
+// up/down, so looking up or down never tips the view over the pole.
+const PITCH_LIMIT: f32 = PI / 2.0 - 0.001;
+
+/// A navigable ASCII donut (or mesh) renderer for the terminal.
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Args {
+    /// Wavefront .obj mesh to render instead of the donut
+    #[arg(long)]
+    mesh: Option<String>,
+
+    /// Grayscale PGM image, or a directory of PGM frames, to play back as ASCII instead of the donut
+    #[arg(long)]
+    frames: Option<String>,
+
+    /// TOML config file overriding the built-in render defaults
+    #[arg(long)]
+    config: Option<String>,
+
+    /// Inner tube radius of the torus
+    #[arg(long)]
+    r1: Option<f32>,
+    /// Outer radius of the torus
+    #[arg(long)]
+    r2: Option<f32>,
+    /// Angular step between successive theta samples
+    #[arg(long)]
+    theta_spacing: Option<f32>,
+    /// Angular step between successive phi samples
+    #[arg(long)]
+    phi_spacing: Option<f32>,
+    /// Target frames per second
+    #[arg(long)]
+    fps: Option<u32>,
+    /// ASCII brightness ramp, darkest to brightest
+    #[arg(long)]
+    ramp: Option<String>,
+    /// Starting speed of the a-axis spin
+    #[arg(long)]
+    a_speed: Option<f32>,
+    /// Starting speed of the b-axis spin
+    #[arg(long)]
+    b_speed: Option<f32>,
+}
+
+// The subset of RenderConfig's fields that may come from a TOML file; every
+// field is optional so a config only needs to mention what it overrides.
+#[derive(Deserialize, Default)]
+struct FileConfig {
+    r1: Option<f32>,
+    r2: Option<f32>,
+    theta_spacing: Option<f32>,
+    phi_spacing: Option<f32>,
+    fps: Option<u32>,
+    ramp: Option<String>,
+    a_speed: Option<f32>,
+    b_speed: Option<f32>,
+}
+
+// Render-tunable parameters, resolved from built-in defaults, then a TOML
+// config file, then CLI flags (each layer overriding the previous one).
+struct RenderConfig {
+    r1: f32,
+    r2: f32,
+    theta_spacing: f32,
+    phi_spacing: f32,
+    fps: u32,
+    ramp: String,
+    a_speed: f32,
+    b_speed: f32,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        RenderConfig {
+            r1: 1.0,
+            r2: 2.0,
+            theta_spacing: 0.07,
+            phi_spacing: 0.02,
+            fps: 20,
+            ramp: ".,-~:;=!*#$@%&".to_string(),
+            a_speed: 0.04,
+            b_speed: 0.08,
+        }
+    }
+}
+
+impl RenderConfig {
+    fn merge_file(mut self, file: FileConfig) -> Self {
+        if let Some(v) = file.r1 { self.r1 = v; }
+        if let Some(v) = file.r2 { self.r2 = v; }
+        if let Some(v) = file.theta_spacing { self.theta_spacing = v; }
+        if let Some(v) = file.phi_spacing { self.phi_spacing = v; }
+        if let Some(v) = file.fps { self.fps = v; }
+        if let Some(v) = file.ramp { self.ramp = v; }
+        if let Some(v) = file.a_speed { self.a_speed = v; }
+        if let Some(v) = file.b_speed { self.b_speed = v; }
+        self
+    }
+
+    fn merge_args(mut self, args: &Args) -> Self {
+        if let Some(v) = args.r1 { self.r1 = v; }
+        if let Some(v) = args.r2 { self.r2 = v; }
+        if let Some(v) = args.theta_spacing { self.theta_spacing = v; }
+        if let Some(v) = args.phi_spacing { self.phi_spacing = v; }
+        if let Some(v) = args.fps { self.fps = v; }
+        if let Some(v) = args.ramp.clone() { self.ramp = v; }
+        if let Some(v) = args.a_speed { self.a_speed = v; }
+        if let Some(v) = args.b_speed { self.b_speed = v; }
+        self
+    }
+}
+
+// Load a RenderConfig from built-in defaults, an optional TOML file, and CLI
+// flags, in that override order.
+fn load_config(args: &Args) -> Result<RenderConfig, Box<dyn Error>> {
+    let file_config = match &args.config {
+        Some(path) => toml::from_str(&fs::read_to_string(path)?)?,
+        None => FileConfig::default(),
+    };
+    let config = RenderConfig::default().merge_file(file_config).merge_args(args);
+    if config.theta_spacing <= 0.0 {
+        return Err("theta_spacing must be positive".into());
+    }
+    if config.phi_spacing <= 0.0 {
+        return Err("phi_spacing must be positive".into());
+    }
+    if config.ramp.is_empty() {
+        return Err("ramp must not be empty".into());
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    fn args_with(theta_spacing: Option<f32>, phi_spacing: Option<f32>, ramp: Option<String>) -> Args {
+        Args {
+            mesh: None,
+            frames: None,
+            config: None,
+            r1: None,
+            r2: None,
+            theta_spacing,
+            phi_spacing,
+            fps: None,
+            ramp,
+            a_speed: None,
+            b_speed: None,
+        }
+    }
+
+    #[test]
+    fn rejects_non_positive_theta_spacing() {
+        let args = args_with(Some(0.0), None, None);
+        assert!(load_config(&args).is_err());
+    }
+
+    #[test]
+    fn rejects_non_positive_phi_spacing() {
+        let args = args_with(None, Some(-0.01), None);
+        assert!(load_config(&args).is_err());
+    }
+
+    #[test]
+    fn rejects_empty_ramp() {
+        let args = args_with(None, None, Some(String::new()));
+        assert!(load_config(&args).is_err());
+    }
+
+    #[test]
+    fn accepts_defaults() {
+        let args = args_with(None, None, None);
+        assert!(load_config(&args).is_ok());
+    }
+}
+
+// How many recent frame times the min/max HUD stats are tracked over.
+const STATS_WINDOW: usize = 120;
+
+// Tracks render performance: the current frame time, an exponential moving
+// average, and the min/max over a sliding window of recent frames.
+struct FrameStats {
+    current_ms: f32,
+    avg_ms: f32,
+    window: VecDeque<f32>,
+}
+
+impl FrameStats {
+    fn new() -> Self {
+        FrameStats { current_ms: 0.0, avg_ms: 0.0, window: VecDeque::new() }
+    }
+
+    fn record(&mut self, dt: f32) {
+        let ms = dt * 1000.0;
+        self.current_ms = ms;
+        self.avg_ms = if self.avg_ms == 0.0 { ms } else { self.avg_ms * 0.9 + ms * 0.1 };
+        self.window.push_back(ms);
+        if self.window.len() > STATS_WINDOW {
+            self.window.pop_front();
+        }
+    }
+
+    fn min_ms(&self) -> f32 {
+        self.window.iter().cloned().fold(f32::INFINITY, f32::min)
+    }
+
+    fn max_ms(&self) -> f32 {
+        self.window.iter().cloned().fold(0.0, f32::max)
+    }
+
+    fn hud_line(&self) -> String {
+        format!(
+            "t={:.1}ms fps={:.0} [{:.1}/{:.1}/{:.1}]",
+            self.current_ms,
+            1000.0 / self.avg_ms.max(0.001),
+            self.min_ms(),
+            self.avg_ms,
+            self.max_ms(),
+        )
+    }
+}
+
+// Write a HUD line into the top-left of the output buffer, clipped to width.
+fn draw_hud(output: &mut [char], width: usize, text: &str) {
+    for (i, ch) in text.chars().enumerate().take(width) {
+        output[i] = ch;
+    }
+}
+
+// Clear the screen, print a rendered frame buffer row by row, and flush
+// stdout so it's displayed immediately. Shared by every renderer.
+fn blit(output: &[char], width: usize) -> Result<(), Box<dyn Error>> {
+    print!("\x1B[H");
+    for (k, &ch) in output.iter().enumerate() {
+        if k % width == 0 {
+            println!();
+        }
+        print!("{ch}");
+    }
+    stdout().flush()?;
+    Ok(())
+}
+
+// Map a luminance value in [0, 1] onto a character in the brightness ramp.
+fn ramp_char(ramp: &[char], l: f32) -> char {
+    let max_idx = (ramp.len() - 1) as f32;
+    let index = (l * max_idx).clamp(0.0, max_idx).floor() as usize;
+    ramp[index]
+}
+
+// A free-fly camera described by position and yaw/pitch, used to build the
+// view transform that replaces the old fixed `k1`/`k2` projection.
+struct Camera {
+    position: [f32; 3],
+    yaw: f32,
+    pitch: f32,
+    fovy: f32,
+    aspect: f32,
+}
+
+impl Camera {
+    fn new(aspect: f32) -> Self {
+        Camera {
+            position: [0.0, 0.0, -5.0],
+            yaw: 0.0,
+            pitch: 0.0,
+            fovy: 60.0_f32.to_radians(),
+            aspect,
+        }
+    }
+
+    // Forward/right/up basis vectors derived from yaw and pitch.
+    fn basis(&self) -> ([f32; 3], [f32; 3], [f32; 3]) {
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+
+        let forward = [cos_yaw * cos_pitch, sin_pitch, sin_yaw * cos_pitch];
+        let right = [-sin_yaw, 0.0, cos_yaw];
+        let up = [
+            right[1] * forward[2] - right[2] * forward[1],
+            right[2] * forward[0] - right[0] * forward[2],
+            right[0] * forward[1] - right[1] * forward[0],
+        ];
+        (forward, right, up)
+    }
+
+    fn translate(&mut self, delta: [f32; 3]) {
+        self.position[0] += delta[0];
+        self.position[1] += delta[1];
+        self.position[2] += delta[2];
+    }
+
+    fn clamp_pitch(&mut self) {
+        self.pitch = self.pitch.clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+}
+
+// The camera's basis vectors and focal length, bundled so per-point
+// projection only needs to take the camera and this one extra argument.
+struct View {
+    forward: [f32; 3],
+    right: [f32; 3],
+    up: [f32; 3],
+    focal: f32,
+}
+
+impl View {
+    fn for_camera(camera: &Camera, height: usize) -> Self {
+        let (forward, right, up) = camera.basis();
+        let focal = height as f32 / (2.0 * (camera.fovy / 2.0).tan());
+        View { forward, right, up, focal }
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = dot(v, v).sqrt();
+    if len > 0.0 { [v[0] / len, v[1] / len, v[2] / len] } else { v }
+}
+
+// Rotate a point by the same pair of spin angles the donut uses: first
+// around the X axis by `a`, then around the Z axis by `b`.
+fn rotate_point(p: [f32; 3], a: f32, b: f32) -> [f32; 3] {
+    let (sin_a, cos_a) = a.sin_cos();
+    let (sin_b, cos_b) = b.sin_cos();
+    let y1 = p[1] * cos_a - p[2] * sin_a;
+    let z1 = p[1] * sin_a + p[2] * cos_a;
+    let x2 = p[0] * cos_b - y1 * sin_b;
+    let y2 = p[0] * sin_b + y1 * cos_b;
+    [x2, y2, z1]
+}
+
+// A triangle mesh loaded from a Wavefront .obj file, rendered with the same
+// ASCII luminance shading and z-buffer as the donut.
+struct Mesh {
+    vertices: Vec<[f32; 3]>,
+    faces: Vec<[usize; 3]>,
+}
+
+// Parse the `v` (vertex) and `f` (face) lines of .obj source text. Faces
+// with more than three vertices are fan-triangulated; texture/normal
+// indices in `f` entries (`v/vt/vn`) are ignored; malformed or
+// out-of-range index tokens (non-numeric, zero, or beyond the vertex
+// count seen so far) are silently skipped rather than failing the whole
+// face.
+fn parse_obj(contents: &str) -> Mesh {
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+
+    for line in contents.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coords: Vec<f32> = tokens.filter_map(|t| t.parse().ok()).collect();
+                if coords.len() >= 3 {
+                    vertices.push([coords[0], coords[1], coords[2]]);
+                }
+            }
+            Some("f") => {
+                let indices: Vec<usize> = tokens
+                    .filter_map(|t| t.split('/').next())
+                    .filter_map(|t| t.parse::<usize>().ok())
+                    .filter(|&i| i >= 1 && i <= vertices.len())
+                    .map(|i| i - 1)
+                    .collect();
+                for i in 1..indices.len().saturating_sub(1) {
+                    faces.push([indices[0], indices[i], indices[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Mesh { vertices, faces }
+}
+
+// Load and parse a Wavefront .obj mesh from disk.
+fn load_obj(path: &str) -> Result<Mesh, Box<dyn Error>> {
+    Ok(parse_obj(&fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod obj_tests {
+    use super::*;
+
+    #[test]
+    fn triangulates_a_quad_as_a_fan() {
+        let mesh = parse_obj(
+            "v 0 0 0\n\
+             v 1 0 0\n\
+             v 1 1 0\n\
+             v 0 1 0\n\
+             f 1 2 3 4\n",
+        );
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.faces, vec![[0, 1, 2], [0, 2, 3]]);
+    }
+
+    #[test]
+    fn strips_texture_and_normal_indices() {
+        let mesh = parse_obj(
+            "v 0 0 0\n\
+             v 1 0 0\n\
+             v 1 1 0\n\
+             f 1/1/1 2/2/1 3/3/1\n",
+        );
+        assert_eq!(mesh.faces, vec![[0, 1, 2]]);
+    }
+
+    #[test]
+    fn ignores_faces_with_malformed_indices() {
+        let mesh = parse_obj(
+            "v 0 0 0\n\
+             v 1 0 0\n\
+             v 1 1 0\n\
+             f 1 bogus 3\n",
+        );
+        // The malformed token is dropped, leaving only two indices, which is
+        // too few to form a triangle.
+        assert!(mesh.faces.is_empty());
+    }
+
+    #[test]
+    fn drops_zero_index_instead_of_underflowing() {
+        let mesh = parse_obj(
+            "v 0 0 0\n\
+             v 1 0 0\n\
+             v 1 1 0\n\
+             f 0 1 2\n",
+        );
+        // `f`'s indices are 1-based; `0` is invalid OBJ and must not
+        // underflow when converted to a 0-based index.
+        assert!(mesh.faces.is_empty());
+    }
+
+    #[test]
+    fn drops_out_of_range_index() {
+        let mesh = parse_obj(
+            "v 0 0 0\n\
+             v 1 0 0\n\
+             v 1 1 0\n\
+             f 1 2 99\n",
+        );
+        assert!(mesh.faces.is_empty());
+    }
+}
+
+// Transform a world-space point into screen space through the camera,
+// returning `None` if the point is behind the camera.
+fn project_point(p: [f32; 3], camera: &Camera, view: &View, width: usize, height: usize) -> Option<(f32, f32, f32)> {
+    let delta = sub(p, camera.position);
+    let x_cam = dot(delta, view.right);
+    let y_cam = dot(delta, view.up);
+    let z_cam = dot(delta, view.forward);
+    if z_cam <= 0.01 {
+        return None;
+    }
+    let xp = width as f32 / 2.0 + (x_cam / z_cam) * view.focal * camera.aspect;
+    let yp = height as f32 / 2.0 - (y_cam / z_cam) * view.focal;
+    Some((xp, yp, z_cam))
+}
+
+// Direction the mesh is lit from, used to shade faces via the same
+// luminance ramp the donut uses.
+const LIGHT_DIR: [f32; 3] = [-0.4, 0.4, -0.8];
+
+// Render an arbitrary triangle mesh with backface culling and a
+// barycentric-fill rasterizer, sharing the output/z-buffer scheme and
+// luminance ramp `render_frame` uses for the donut.
+#[allow(clippy::too_many_arguments)]
+fn render_mesh_frame(
+    mesh: &Mesh,
+    a: f32,
+    b: f32,
+    width: usize,
+    height: usize,
+    camera: &Camera,
+    config: &RenderConfig,
+    hud: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let mut output = vec![' '; width * height];
+    let mut zbuffer = vec![f32::INFINITY; width * height];
+
+    let view = View::for_camera(camera, height);
+    let light_dir = normalize(LIGHT_DIR);
+    let ramp: Vec<char> = config.ramp.chars().collect();
+
+    for face in &mesh.faces {
+        let p0 = rotate_point(mesh.vertices[face[0]], a, b);
+        let p1 = rotate_point(mesh.vertices[face[1]], a, b);
+        let p2 = rotate_point(mesh.vertices[face[2]], a, b);
+
+        let normal = normalize(cross(sub(p1, p0), sub(p2, p0)));
+        let centroid = [
+            (p0[0] + p1[0] + p2[0]) / 3.0,
+            (p0[1] + p1[1] + p2[1]) / 3.0,
+            (p0[2] + p1[2] + p2[2]) / 3.0,
+        ];
+        let view_dir = sub(centroid, camera.position);
+        if dot(normal, view_dir) >= 0.0 {
+            continue; // Backface: facing away from the camera
+        }
+
+        let (Some(s0), Some(s1), Some(s2)) = (
+            project_point(p0, camera, &view, width, height),
+            project_point(p1, camera, &view, width, height),
+            project_point(p2, camera, &view, width, height),
+        ) else {
+            continue; // One or more vertices behind the camera
+        };
+
+        let area = (s1.0 - s0.0) * (s2.1 - s0.1) - (s2.0 - s0.0) * (s1.1 - s0.1);
+        if area.abs() < 1e-6 {
+            continue; // Degenerate triangle in screen space
+        }
+
+        let min_x = s0.0.min(s1.0).min(s2.0).floor().max(0.0) as usize;
+        let max_x = (s0.0.max(s1.0).max(s2.0).ceil() as isize).min(width as isize - 1).max(0) as usize;
+        let min_y = s0.1.min(s1.1).min(s2.1).floor().max(0.0) as usize;
+        let max_y = (s0.1.max(s1.1).max(s2.1).ceil() as isize).min(height as isize - 1).max(0) as usize;
+
+        let l = dot(normal, light_dir).max(0.0);
+        let ch = ramp_char(&ramp, l);
+
+        for py in min_y..=max_y {
+            for px in min_x..=max_x {
+                let p = (px as f32 + 0.5, py as f32 + 0.5);
+                let w0 = (s1.0 - p.0) * (s2.1 - p.1) - (s2.0 - p.0) * (s1.1 - p.1);
+                let w1 = (s2.0 - p.0) * (s0.1 - p.1) - (s0.0 - p.0) * (s2.1 - p.1);
+                let w2 = (s0.0 - p.0) * (s1.1 - p.1) - (s1.0 - p.0) * (s0.1 - p.1);
+                if w0 * area >= 0.0 && w1 * area >= 0.0 && w2 * area >= 0.0 {
+                    let z = (w0 * s0.2 + w1 * s1.2 + w2 * s2.2) / area;
+                    let index = px + width * py;
+                    if z < zbuffer[index] {
+                        zbuffer[index] = z;
+                        output[index] = ch;
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(text) = hud {
+        draw_hud(&mut output, width, text);
+    }
+
+    blit(&output, width)
+}
+
+// A single decoded grayscale frame, played back onto the terminal grid with
+// the same luminance ramp the donut and mesh renderers use.
+struct GrayFrame {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+// Read the next whitespace-delimited token from a PGM header, skipping
+// `#`-prefixed comments, and advance `pos` past it.
+fn read_pgm_token(data: &[u8], pos: &mut usize) -> Result<String, Box<dyn Error>> {
+    loop {
+        while *pos < data.len() && (data[*pos] as char).is_whitespace() {
+            *pos += 1;
+        }
+        if *pos < data.len() && data[*pos] == b'#' {
+            while *pos < data.len() && data[*pos] != b'\n' {
+                *pos += 1;
+            }
+            continue;
+        }
+        break;
+    }
+    let start = *pos;
+    while *pos < data.len() && !(data[*pos] as char).is_whitespace() {
+        *pos += 1;
+    }
+    if start == *pos {
+        return Err("unexpected end of PGM header".into());
+    }
+    Ok(String::from_utf8_lossy(&data[start..*pos]).into_owned())
+}
+
+// Load a grayscale PGM image (binary P5 or ASCII P2), normalizing samples to
+// the 0-255 range regardless of the file's declared maxval.
+fn load_pgm(path: &Path) -> Result<GrayFrame, Box<dyn Error>> {
+    let data = fs::read(path)?;
+    let mut pos = 0;
+    let magic = read_pgm_token(&data, &mut pos)?;
+    if magic != "P5" && magic != "P2" {
+        return Err(format!("unsupported PGM magic number: {magic}").into());
+    }
+    let width: usize = read_pgm_token(&data, &mut pos)?.parse()?;
+    let height: usize = read_pgm_token(&data, &mut pos)?.parse()?;
+    let maxval: usize = read_pgm_token(&data, &mut pos)?.parse()?;
+
+    let mut samples: Vec<usize> = Vec::with_capacity(width * height);
+    if magic == "P5" {
+        pos += 1; // Single whitespace byte separating the header from binary data
+        // One byte per sample, or two big-endian bytes when maxval exceeds 255.
+        let bytes = if maxval > 255 { 2 } else { 1 };
+        for sample in data[pos..pos + width * height * bytes].chunks(bytes) {
+            samples.push(sample.iter().fold(0, |acc, &byte| (acc << 8) | byte as usize));
+        }
+    } else {
+        while samples.len() < width * height {
+            samples.push(read_pgm_token(&data, &mut pos)?.parse::<usize>()?);
+        }
+    }
+    let pixels = samples.iter().map(|&s| (s.min(maxval) * 255 / maxval) as u8).collect();
+
+    Ok(GrayFrame { width, height, pixels })
+}
+
+#[cfg(test)]
+mod pgm_tests {
+    use super::*;
+
+    #[test]
+    fn read_pgm_token_skips_whitespace_and_comments() {
+        let data = b"  # a comment\n P5 128";
+        let mut pos = 0;
+        assert_eq!(read_pgm_token(data, &mut pos).unwrap(), "P5");
+        assert_eq!(read_pgm_token(data, &mut pos).unwrap(), "128");
+    }
+
+    #[test]
+    fn read_pgm_token_errs_at_end_of_data() {
+        let data = b"P5  ";
+        let mut pos = 0;
+        read_pgm_token(data, &mut pos).unwrap();
+        assert!(read_pgm_token(data, &mut pos).is_err());
+    }
+
+    #[test]
+    fn load_pgm_decodes_ascii_p2() {
+        let path = std::env::temp_dir().join("donut_ascii_pgm_test_p2.pgm");
+        fs::write(&path, b"P2\n2 2\n255\n0 85 170 255\n").unwrap();
+        let frame = load_pgm(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!((frame.width, frame.height), (2, 2));
+        assert_eq!(frame.pixels, vec![0, 85, 170, 255]);
+    }
+
+    #[test]
+    fn load_pgm_decodes_binary_p5_and_rescales_maxval() {
+        let path = std::env::temp_dir().join("donut_ascii_pgm_test_p5.pgm");
+        let mut data = b"P5\n2 1\n15\n".to_vec();
+        data.extend_from_slice(&[0, 15]);
+        fs::write(&path, &data).unwrap();
+        let frame = load_pgm(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!((frame.width, frame.height), (2, 1));
+        assert_eq!(frame.pixels, vec![0, 255]);
+    }
+
+    #[test]
+    fn load_pgm_rejects_unknown_magic() {
+        let path = std::env::temp_dir().join("donut_ascii_pgm_test_bad.pgm");
+        fs::write(&path, b"P3\n2 2\n255\n").unwrap();
+        let result = load_pgm(&path);
+        fs::remove_file(&path).ok();
+        assert!(result.is_err());
+    }
+}
+
+// Load a single PGM file, or every entry of a directory (sorted by name) as a
+// frame sequence. Entries are not filtered by type: any entry that is not a
+// PGM file makes the whole load fail.
+fn load_frame_sequence(path: &str) -> Result<Vec<GrayFrame>, Box<dyn Error>> {
+    let path = Path::new(path);
+    if fs::metadata(path)?.is_dir() {
+        let mut entries: Vec<_> = fs::read_dir(path)?.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+        entries.sort();
+        entries.iter().map(|p| load_pgm(p)).collect()
+    } else {
+        Ok(vec![load_pgm(path)?])
+    }
+}
+
+// Linearly remap `value` from [in_min, in_max] to [out_min, out_max].
+fn map_range(value: f32, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> f32 {
+    out_min + (value - in_min) * (out_max - out_min) / (in_max - in_min)
+}
+
+// Resample a grayscale frame onto the terminal grid and render it through
+// the luminance ramp, reusing the buffer-and-flush output path.
+fn render_gray_frame(
+    frame: &GrayFrame,
+    width: usize,
+    height: usize,
+    config: &RenderConfig,
+    hud: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let mut output = vec![' '; width * height];
+    let ramp: Vec<char> = config.ramp.chars().collect();
+
+    for y in 0..height {
+        let src_y = (map_range(y as f32, 0.0, height as f32, 0.0, frame.height as f32) as usize)
+            .min(frame.height.saturating_sub(1));
+        for x in 0..width {
+            let src_x = (map_range(x as f32, 0.0, width as f32, 0.0, frame.width as f32) as usize)
+                .min(frame.width.saturating_sub(1));
+            let brightness = frame.pixels[src_y * frame.width + src_x] as f32 / 255.0;
+            output[y * width + x] = ramp_char(&ramp, brightness);
+        }
+    }
+
+    if let Some(text) = hud {
+        draw_hud(&mut output, width, text);
+    }
+
+    blit(&output, width)
+}
+
+// Q10 fixed-point helpers used by the integer renderer to avoid a sin_cos
+// call for every step of the hot theta/phi loops.
+mod fixed {
+    pub const ONE: i32 = 1024;
+
+    // Rotate the unit vector (x, y) by the small angle encoded as (mul, shift),
+    // then renormalize back onto the unit circle to correct accumulated drift.
+    pub fn step(x: &mut i32, y: &mut i32, mul: i32, shift: u32) {
+        let temp = *x;
+        *x -= (mul * *y) >> shift;
+        *y += (mul * temp) >> shift;
+        let temp = (3 * ONE * ONE - *x * *x - *y * *y) >> 11;
+        *x = (*x * temp) >> 10;
+        *y = (*y * temp) >> 10;
+    }
+
+    // Precompute the (mul, shift) pair that approximates rotation by `angle`
+    // radians through the step() recurrence above.
+    pub fn rotation_for(angle: f32) -> (i32, u32) {
+        let shift = 16;
+        ((angle * (1_i64 << shift) as f32) as i32, shift)
+    }
+
+    pub fn to_f32(v: i32) -> f32 {
+        v as f32 / ONE as f32
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Stepping `step()` forward n times from (ONE, 0) should track
+        // cos/sin of n * angle, within the precision the Q10 renormalization
+        // allows.
+        #[test]
+        fn step_tracks_sin_cos() {
+            let angle = 0.07_f32;
+            let (mul, shift) = rotation_for(angle);
+            let (mut x, mut y) = (ONE, 0);
+            for n in 1..=50 {
+                step(&mut x, &mut y, mul, shift);
+                let expected = n as f32 * angle;
+                assert!((to_f32(x) - expected.cos()).abs() < 0.03, "cos drift at n={n}");
+                assert!((to_f32(y) - expected.sin()).abs() < 0.03, "sin drift at n={n}");
+            }
+        }
+
+        // The renormalization in step() should keep (x, y) on the unit
+        // circle (scaled by ONE) instead of drifting away from it.
+        #[test]
+        fn step_stays_normalized() {
+            let (mul, shift) = rotation_for(0.02);
+            let (mut x, mut y) = (ONE, 0);
+            for _ in 0..500 {
+                step(&mut x, &mut y, mul, shift);
+                let mag = ((x * x + y * y) as f32).sqrt();
+                assert!((mag - ONE as f32).abs() < 5.0, "magnitude drifted to {mag}");
+            }
+        }
+    }
+}
+
+// Spring constants for the speed-easing dynamics below: how strongly the
+// speed is pulled toward its target, and how much velocity is damped.
+const SPRING_K: f32 = 15.0;
+const SPRING_DAMPING: f32 = 0.1;
+
+// Largest `dt` fed to `spring_step` in one call: caps how far a single slow
+// frame (terminal resize, a stalled frame-sequence load, ...) can push the
+// RK4 integration in one step.
+const SPRING_MAX_DT: f32 = 0.1;
+
+// A 1D spring-damper state (position and velocity) used to ease a_speed/
+// b_speed toward their targets instead of snapping instantly.
+#[derive(Clone, Copy)]
+struct SpringState {
+    x: f32,
+    v: f32,
+}
+
+#[derive(Clone, Copy)]
+struct Derivative {
+    dx: f32,
+    dv: f32,
+}
+
+fn spring_accel(s: SpringState, target: f32) -> f32 {
+    -SPRING_K * (s.x - target) - SPRING_DAMPING * s.v
+}
+
+// Advance a temp copy of `s` by `dt` along `d`, then sample the derivative there.
+fn spring_eval(s: SpringState, dt: f32, d: Derivative, target: f32) -> Derivative {
+    let advanced = SpringState { x: s.x + d.dx * dt, v: s.v + d.dv * dt };
+    Derivative { dx: advanced.v, dv: spring_accel(advanced, target) }
+}
+
+// RK4-integrate the spring one frame toward `target`, using the real elapsed
+// frame time as `dt` so the easing is frame-rate independent.
+fn spring_step(s: &mut SpringState, dt: f32, target: f32) {
+    let d1 = Derivative { dx: s.v, dv: spring_accel(*s, target) };
+    let d2 = spring_eval(*s, dt * 0.5, d1, target);
+    let d3 = spring_eval(*s, dt * 0.5, d2, target);
+    let d4 = spring_eval(*s, dt, d3, target);
+    s.x += dt / 6.0 * (d1.dx + 2.0 * d2.dx + 2.0 * d3.dx + d4.dx);
+    s.v += dt / 6.0 * (d1.dv + 2.0 * d2.dv + 2.0 * d3.dv + d4.dv);
+}
+
+#[cfg(test)]
+mod spring_tests {
+    use super::*;
+
+    #[test]
+    fn large_unclamped_dt_overshoots_wildly() {
+        // Documents the failure mode SPRING_MAX_DT guards against: a 2s
+        // frame hitch fed straight into spring_step reverses direction and
+        // lands far below zero instead of easing toward the target.
+        let mut s = SpringState { x: 0.04, v: 0.0 };
+        spring_step(&mut s, 2.0, 0.08);
+        assert!(s.x < 0.0, "expected an overshoot past zero, got {}", s.x);
+    }
+
+    #[test]
+    fn clamped_dt_eases_toward_target_without_blowing_up() {
+        let mut s = SpringState { x: 0.04, v: 0.0 };
+        let dt = 2.0_f32.min(SPRING_MAX_DT);
+        spring_step(&mut s, dt, 0.08);
+        assert!((s.x - 0.04).abs() < 0.1, "single clamped step moved too far: {}", s.x);
+        assert!(s.x > 0.0, "clamped step should not reverse direction, got {}", s.x);
+    }
+}
+
+// Function to get the terminal size
+fn get_terminal_size() -> (usize, usize) {
+    // Retrieve the terminal size or set a default size if unsuccessful
+    let (width, height) = size().unwrap_or((240, 80));
+    (width as usize, height as usize)
+}
+
+// Function to render each frame of the ASCII donut
+fn render_frame(
+    a: f32,
+    b: f32,
+    width: usize,
+    height: usize,
+    camera: &Camera,
+    config: &RenderConfig,
+    hud: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    // Initialize the output buffer and z-buffer
+    let mut output = vec![' '; width * height];
+    let mut zbuffer = vec![f32::INFINITY; width * height];
+
+    // Constants and pre-calculated values for rendering
+    let theta_spacing = config.theta_spacing;
+    let phi_spacing = config.phi_spacing;
+    let r1 = config.r1;
+    let r2 = config.r2;
+    let ramp: Vec<char> = config.ramp.chars().collect();
+
+    let (sin_a, cos_a) = a.sin_cos();
+    let (sin_b, cos_b) = b.sin_cos();
+
+    let (forward, right, up) = camera.basis();
+    let focal = height as f32 / (2.0 * (camera.fovy / 2.0).tan());
+
+    // Loop through each point in the 3D space and calculate its projection onto the 2D screen
+    for theta in (0..).map(|i| i as f32 * theta_spacing).take_while(|&theta| theta < 2.0 * PI) {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        for phi in (0..).map(|i| i as f32 * phi_spacing).take_while(|&phi| phi < 2.0 * PI) {
+            let (sin_phi, cos_phi) = phi.sin_cos();
+            let circle_x = r2 + r1 * cos_theta;
+            let x = circle_x * (cos_b * cos_phi + sin_a * sin_b * sin_phi) - r1 * cos_a * sin_b * sin_phi;
+            let y = circle_x * sin_phi * cos_b - r1 * sin_b * sin_phi * cos_a;
+            let z = cos_a * circle_x * sin_phi + sin_a * r1 * cos_phi;
+
+            // Transform the world-space torus point into camera space.
+            let delta = [x - camera.position[0], y - camera.position[1], z - camera.position[2]];
+            let x_cam = delta[0] * right[0] + delta[1] * right[1] + delta[2] * right[2];
+            let y_cam = delta[0] * up[0] + delta[1] * up[1] + delta[2] * up[2];
+            let z_cam = delta[0] * forward[0] + delta[1] * forward[1] + delta[2] * forward[2];
+
+            if z_cam <= 0.01 {
+                continue; // Behind the camera
+            }
+
+            let xp = (width as f32 / 2.0 + (x_cam / z_cam) * focal * camera.aspect) as isize;
+            let yp = (height as f32 / 2.0 - (y_cam / z_cam) * focal) as isize;
+            if xp >= 0 && xp < width as isize && yp >= 0 && yp < height as isize {
+                let index = xp as usize + width * yp as usize;
+                let l = cos_phi * cos_theta * sin_b - cos_a * cos_theta * sin_phi - sin_a * sin_theta + cos_b * (cos_a * sin_theta - cos_theta * sin_a * sin_phi);
+                if l > 0.0 && z_cam < zbuffer[index] {  // Depth test
+                    zbuffer[index] = z_cam;
+                    output[index] = ramp_char(&ramp, l);
+                }
+            }
+        }
+    }
+
+    if let Some(text) = hud {
+        draw_hud(&mut output, width, text);
+    }
+
+    blit(&output, width)
+}
+
+// Integer/fixed-point variant of `render_frame` for slow terminals: the
+// theta/phi sines and cosines are advanced with the Q10 rotation recurrence
+// instead of calling `sin_cos` on every iteration of the hot loop.
+fn render_frame_fixed(
+    a: f32,
+    b: f32,
+    width: usize,
+    height: usize,
+    camera: &Camera,
+    config: &RenderConfig,
+    hud: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    use fixed::{rotation_for, step, to_f32, ONE};
+
+    // Initialize the output buffer and z-buffer
+    let mut output = vec![' '; width * height];
+    let mut zbuffer = vec![i32::MAX; width * height];
+
+    let theta_spacing = config.theta_spacing;
+    let phi_spacing = config.phi_spacing;
+    let r1 = config.r1;
+    let r1_fx = (r1 * ONE as f32) as i32;
+    let r2_fx = (config.r2 * ONE as f32) as i32;
+    let ramp: Vec<char> = config.ramp.chars().collect();
+
+    let (sin_a, cos_a) = a.sin_cos();
+    let (sin_b, cos_b) = b.sin_cos();
+
+    let (forward, right, up) = camera.basis();
+    let focal = height as f32 / (2.0 * (camera.fovy / 2.0).tan());
+
+    let theta_count = (2.0 * PI / theta_spacing) as usize;
+    let phi_count = (2.0 * PI / phi_spacing) as usize;
+    let (theta_mul, theta_shift) = rotation_for(theta_spacing);
+    let (phi_mul, phi_shift) = rotation_for(phi_spacing);
+
+    // Running fixed-point cos/sin of theta, advanced by the recurrence above.
+    let (mut cos_theta_fx, mut sin_theta_fx) = (ONE, 0);
+    for _ in 0..theta_count {
+        let cos_theta = to_f32(cos_theta_fx);
+        let sin_theta = to_f32(sin_theta_fx);
+        let circle_x_fx = r2_fx + ((r1_fx * cos_theta_fx) >> 10);
+
+        // Running fixed-point cos/sin of phi, reset at the start of each theta ring.
+        let (mut cos_phi_fx, mut sin_phi_fx) = (ONE, 0);
+        for _ in 0..phi_count {
+            let cos_phi = to_f32(cos_phi_fx);
+            let sin_phi = to_f32(sin_phi_fx);
+            let circle_x = to_f32(circle_x_fx);
+
+            let x = circle_x * (cos_b * cos_phi + sin_a * sin_b * sin_phi) - r1 * cos_a * sin_b * sin_phi;
+            let y = circle_x * sin_phi * cos_b - r1 * sin_b * sin_phi * cos_a;
+            let z = cos_a * circle_x * sin_phi + sin_a * r1 * cos_phi;
+
+            let delta = [x - camera.position[0], y - camera.position[1], z - camera.position[2]];
+            let x_cam = delta[0] * right[0] + delta[1] * right[1] + delta[2] * right[2];
+            let y_cam = delta[0] * up[0] + delta[1] * up[1] + delta[2] * up[2];
+            let z_cam = delta[0] * forward[0] + delta[1] * forward[1] + delta[2] * forward[2];
+
+            if z_cam <= 0.01 {
+                step(&mut cos_phi_fx, &mut sin_phi_fx, phi_mul, phi_shift);
+                continue; // Behind the camera
+            }
+
+            let xp = (width as f32 / 2.0 + (x_cam / z_cam) * focal * camera.aspect) as isize;
+            let yp = (height as f32 / 2.0 - (y_cam / z_cam) * focal) as isize;
+            if xp >= 0 && xp < width as isize && yp >= 0 && yp < height as isize {
+                let index = xp as usize + width * yp as usize;
+                // Luminance dot product, evaluated from the same fixed-point
+                // theta/phi values the projection above used.
+                let l = cos_phi * cos_theta * sin_b - cos_a * cos_theta * sin_phi - sin_a * sin_theta + cos_b * (cos_a * sin_theta - cos_theta * sin_a * sin_phi);
+                let z_scaled = (z_cam * ONE as f32) as i32;
+                if l > 0.0 && z_scaled < zbuffer[index] {  // Depth test
+                    zbuffer[index] = z_scaled;
+                    output[index] = ramp_char(&ramp, l);
+                }
+            }
+
+            step(&mut cos_phi_fx, &mut sin_phi_fx, phi_mul, phi_shift);
+        }
+
+        step(&mut cos_theta_fx, &mut sin_theta_fx, theta_mul, theta_shift);
+    }
+
+    if let Some(text) = hud {
+        draw_hud(&mut output, width, text);
+    }
+
+    blit(&output, width)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let config = load_config(&args)?;
+
+    // An optional .obj mesh path swaps the donut for a mesh.
+    let mesh = args.mesh.as_deref().map(load_obj).transpose()?;
+    // An optional PGM image (or directory of them) swaps the donut for an
+    // ASCII frame player, taking priority over both the donut and the mesh.
+    let frames = args.frames.as_deref().map(load_frame_sequence).transpose()?;
+
+    // Initialize terminal and get its size
+    let (width, height) = get_terminal_size();
+    let (tx, rx) = mpsc::channel();  // Create a channel for inter-thread communication
+    let handle = thread::spawn(move || {
+        // Thread to handle keyboard input
+        loop {
+            if let Ok(true) = event::poll(Duration::from_millis(100)) {
+                if let Ok(Event::Key(key_event)) = event::read() {
+                    tx.send(key_event).unwrap();  // Send key events to the main thread
+                }
+            }
+        }
+    });
+
+    // Initialize parameters for the donut animation
+    let mut a = 0.0_f32;
+    let mut b = 0.0_f32;
+    // Actual spin speeds, eased toward their targets by the spring-damper below.
+    let mut a_spring = SpringState { x: config.a_speed, v: 0.0 };
+    let mut b_spring = SpringState { x: config.b_speed, v: 0.0 };
+    let mut a_target = config.a_speed;
+    let mut b_target = config.b_speed;
+    let mut last_dt = 0.05_f32;
+    let mut running = true;
+
+    // Free-fly camera used to navigate around the donut
+    let mut camera = Camera::new(width as f32 / height as f32);
+    let move_speed = 0.2;
+    let look_speed = 0.04;
+    // Toggle between the f32 and fixed-point (Q10) renderers, for slow terminals.
+    let mut use_fixed_point = false;
+    // Toggleable frame-timing HUD
+    let mut show_hud = false;
+    let mut stats = FrameStats::new();
+
+    // Frame-sequence playback state
+    let mut frame_index = 0usize;
+    let mut playback_paused = false;
+    let mut time_multiplier = 1.0_f32;
+    let mut frame_accum = Duration::ZERO;
+
+    execute!(stdout(), EnterAlternateScreen)?;  // Switch to alternate screen buffer
+    terminal::enable_raw_mode()?;  // Enable raw mode to handle keyboard input
+
+    // Main loop for rendering frames and handling input
+    while running {
+        let now = Instant::now();  // Record current time
+        let (forward, right, _up) = camera.basis();
+        // Process keyboard input
+        while let Ok(KeyEvent { code, .. }) = rx.try_recv() {
+            match code {
+                KeyCode::Up => a_target += 0.01,
+                KeyCode::Down => a_target -= 0.01,
+                KeyCode::Right => b_target += 0.01,
+                KeyCode::Left => b_target -= 0.01,
+                KeyCode::Char('r') => { a_target = config.a_speed; b_target = config.b_speed; },  // Reset speeds
+                KeyCode::Char('p') => { a_target = 0.0; b_target = 0.0; playback_paused = !playback_paused; },  // Pause animation/playback
+                KeyCode::Char('+') | KeyCode::Char('=') => time_multiplier = (time_multiplier * 1.25).min(8.0),
+                KeyCode::Char('-') => time_multiplier = (time_multiplier / 1.25).max(0.05),
+                // Translate the camera along its forward/right axes
+                KeyCode::Char('w') => camera.translate([forward[0] * move_speed, forward[1] * move_speed, forward[2] * move_speed]),
+                KeyCode::Char('s') => camera.translate([-forward[0] * move_speed, -forward[1] * move_speed, -forward[2] * move_speed]),
+                KeyCode::Char('a') => camera.translate([-right[0] * move_speed, -right[1] * move_speed, -right[2] * move_speed]),
+                KeyCode::Char('d') => camera.translate([right[0] * move_speed, right[1] * move_speed, right[2] * move_speed]),
+                KeyCode::Char('q') => camera.translate([0.0, move_speed, 0.0]),
+                KeyCode::Char('e') => camera.translate([0.0, -move_speed, 0.0]),
+                // Look around with the vim-style H/J/K/L keys
+                KeyCode::Char('h') => camera.yaw -= look_speed,
+                KeyCode::Char('l') => camera.yaw += look_speed,
+                KeyCode::Char('j') => { camera.pitch -= look_speed; camera.clamp_pitch(); },
+                KeyCode::Char('k') => { camera.pitch += look_speed; camera.clamp_pitch(); },
+                KeyCode::Char('i') => use_fixed_point = !use_fixed_point,  // Toggle fixed-point renderer
+                KeyCode::Char('f') => show_hud = !show_hud,  // Toggle the frame-timing HUD
+                KeyCode::Esc => { running = false; break; },  // Exit program
+                _ => {}
+            }
+        }
+
+        // Ease the spin speeds toward their targets using the real frame
+        // time, capped so a stalled/slow frame (resize, a slow frame-sequence
+        // load, ...) can't fling the spring state far past its target.
+        let spring_dt = last_dt.min(SPRING_MAX_DT);
+        spring_step(&mut a_spring, spring_dt, a_target);
+        spring_step(&mut b_spring, spring_dt, b_target);
+
+        let hud_line = show_hud.then(|| stats.hud_line());
+
+        // Render the current frame: a loaded frame sequence takes priority
+        // over a mesh, which takes priority over the donut (float or fixed-point).
+        if let Some(frame) = frames.as_ref().filter(|f| !f.is_empty()).map(|f| &f[frame_index]) {
+            render_gray_frame(frame, width, height, &config, hud_line.as_deref())?;
+        } else if let Some(mesh) = &mesh {
+            render_mesh_frame(mesh, a, b, width, height, &camera, &config, hud_line.as_deref())?;
+        } else if use_fixed_point {
+            render_frame_fixed(a, b, width, height, &camera, &config, hud_line.as_deref())?;
+        } else {
+            render_frame(a, b, width, height, &camera, &config, hud_line.as_deref())?;
+        }
+        // Update parameters for next frame
+        a += a_spring.x;
+        b += b_spring.x;
+        let elapsed = now.elapsed();  // Calculate time elapsed since the start of the frame
+        stats.record(elapsed.as_secs_f32());
+        last_dt = elapsed.as_secs_f32().max(1e-4);
+
+        // Advance frame-sequence playback at `time_multiplier` times the target FPS.
+        let playing_frames = (!playback_paused).then_some(frames.as_ref()).flatten().filter(|f| !f.is_empty());
+        if let Some(frames) = playing_frames {
+            frame_accum += elapsed;
+            let interval_ms = (1000.0 / config.fps.max(1) as f32) / time_multiplier.max(0.01);
+            let interval = Duration::from_millis(interval_ms.max(1.0) as u64);
+            while frame_accum >= interval {
+                frame_accum -= interval;
+                frame_index = (frame_index + 1) % frames.len();
+            }
+        }
+
+        let frame_budget = Duration::from_millis(1000 / config.fps.max(1) as u64);
+        let delay = frame_budget.saturating_sub(elapsed);  // Calculate delay to maintain the target FPS
+        thread::sleep(delay);  // Wait for the remaining time to maintain frame rate
+    }
+
+    // Clean up: disable raw mode, switch back to main screen buffer, and join the keyboard input thread
+    terminal::disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen)?;
+    handle.join().ok();
+
+    Ok(())
+}